@@ -0,0 +1,42 @@
+use syn::{FnArg, ImplItemMethod, Lit, Type};
+
+/// Looks up an attribute on a method by its path, e.g. `find_attr(method, "cycles")`
+/// for `#[cycles(100)]`.
+pub fn find_attr<'a>(method: &'a ImplItemMethod, name: &str) -> Option<&'a syn::Attribute> {
+    method.attrs.iter().find(|attr| attr.path.is_ident(name))
+}
+
+/// Returns `true` when the method carries an attribute with the given name.
+pub fn has_attr(method: &ImplItemMethod, name: &str) -> bool {
+    find_attr(method, name).is_some()
+}
+
+/// Pulls the payload type out of a `#[read]`/`#[write]` method signature,
+/// i.e. the argument following `&self`/`&mut self` and `ctx: Context`.
+///
+/// Returns `None` for methods that only take `&self`/`&mut self` and `ctx`.
+pub fn payload_type(method: &ImplItemMethod) -> Option<Type> {
+    method
+        .sig
+        .inputs
+        .iter()
+        .nth(2)
+        .and_then(|arg| match arg {
+            FnArg::Typed(pat_type) => Some((*pat_type.ty).clone()),
+            FnArg::Receiver(_) => None,
+        })
+}
+
+/// Reads the flat cycle cost out of a `#[cycles(100)]` attribute, if present.
+///
+/// Returns `None` when the method has no `#[cycles]` attribute, or when it
+/// carries an expression-based cost rather than a literal.
+pub fn cycles_literal(method: &ImplItemMethod) -> Option<u64> {
+    let attr = find_attr(method, "cycles")?;
+    let lit: Lit = attr.parse_args().ok()?;
+
+    match lit {
+        Lit::Int(lit_int) => lit_int.base10_parse().ok(),
+        _ => None,
+    }
+}
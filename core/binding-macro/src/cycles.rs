@@ -0,0 +1,56 @@
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::parse::{Parse, ParseStream};
+use syn::{parse_macro_input, Expr, ImplItemMethod, Lit, Token};
+
+/// `#[cycles(expr = <expr>)]`'s argument: an arbitrary expression evaluated
+/// in the method's own scope, so it may reference its parameters.
+struct ExprArg {
+    expr: Expr,
+}
+
+impl Parse for ExprArg {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let ident: syn::Ident = input.parse()?;
+        if ident != "expr" {
+            return Err(syn::Error::new(
+                ident.span(),
+                "expected a literal cycle count or `expr = <expression>`",
+            ));
+        }
+        input.parse::<Token![=]>()?;
+
+        Ok(ExprArg {
+            expr: input.parse()?,
+        })
+    }
+}
+
+/// Parses `#[cycles(..)]`'s argument into the cost expression spliced into
+/// `ctx.sub_cycles(..)`: either a flat literal (`#[cycles(100)]`) or an
+/// `expr = ..` expression that may reference the method's own parameters.
+fn parse_cost(attr: TokenStream) -> TokenStream2 {
+    if let Ok(lit) = syn::parse::<Lit>(attr.clone()) {
+        return quote!(#lit);
+    }
+
+    let ExprArg { expr } = syn::parse::<ExprArg>(attr)
+        .unwrap_or_else(|e| panic!("#[cycles]: {}", e));
+    quote!(#expr)
+}
+
+/// Splices `ctx.sub_cycles(<cost>)?;` at the top of a `#[cycles(..)]`-marked
+/// method body, so the deduction happens before the user's body runs and a
+/// failure short-circuits via `?`.
+pub fn gen_cycles_code(attr: TokenStream, item: TokenStream) -> TokenStream {
+    let cost = parse_cost(attr);
+    let mut method = parse_macro_input!(item as ImplItemMethod);
+
+    let deduct: syn::Stmt = syn::parse_quote! {
+        ctx.sub_cycles(#cost)?;
+    };
+    method.block.stmts.insert(0, deduct);
+
+    quote!(#method).into()
+}
@@ -99,6 +99,10 @@ pub fn write(_: TokenStream, item: TokenStream) -> TokenStream {
 ///     }
 /// }
 /// ```
+///
+/// The cost can also be an expression referencing the method's own
+/// parameters, e.g. `#[cycles(expr = 21_000 + payload.data.len() as u64 * 68)]`,
+/// so fees can scale with the work a call actually does.
 #[proc_macro_attribute]
 pub fn cycles(attr: TokenStream, item: TokenStream) -> TokenStream {
     gen_cycles_code(attr, item)
@@ -0,0 +1,100 @@
+use proc_macro::TokenStream;
+use syn::{FnArg, GenericParam, ImplItemMethod, PathArguments, ReturnType, Type, Visibility};
+
+fn attr_name(is_write: bool) -> &'static str {
+    if is_write {
+        "write"
+    } else {
+        "read"
+    }
+}
+
+/// Does `ty` name a path whose last segment is `expected`, e.g. does
+/// `ProtocolResult<String>` end in `ProtocolResult`?
+fn type_path_ends_in(ty: &Type, expected: &str) -> bool {
+    matches!(ty, Type::Path(path) if path.path.segments.last().map_or(false, |s| s.ident == expected))
+}
+
+/// Checks the method's sole generic parameter is bound by a trait path
+/// ending in `RequestContext`, e.g. `fn f<Context: RequestContext>(..)`,
+/// and returns that parameter's identifier.
+fn request_context_param(method: &ImplItemMethod) -> Option<&syn::Ident> {
+    let [GenericParam::Type(param)] = method.sig.generics.params.iter().collect::<Vec<_>>()[..] else {
+        return None;
+    };
+
+    let bound = param.bounds.iter().any(|bound| {
+        matches!(bound, syn::TypeParamBound::Trait(trait_bound)
+            if trait_bound.path.segments.last().map_or(false, |s| s.ident == "RequestContext"))
+    });
+
+    bound.then_some(&param.ident)
+}
+
+/// Checks the method returns `ProtocolResult<String>`.
+fn returns_protocol_result_string(method: &ImplItemMethod) -> bool {
+    let ReturnType::Type(_, ty) = &method.sig.output else {
+        return false;
+    };
+    let Type::Path(path) = ty.as_ref() else {
+        return false;
+    };
+    let Some(segment) = path.path.segments.last() else {
+        return false;
+    };
+    if segment.ident != "ProtocolResult" {
+        return false;
+    }
+
+    let PathArguments::AngleBracketed(args) = &segment.arguments else {
+        return false;
+    };
+    matches!(
+        args.args.first(),
+        Some(syn::GenericArgument::Type(ok_ty)) if type_path_ends_in(ok_ty, "String")
+    )
+}
+
+/// Validates a `#[read]`/`#[write]`-marked method's signature and returns it
+/// unchanged, so `#[service]`'s generated dispatch can call into it
+/// directly: private visibility, a `&self`/`&mut self` receiver matching
+/// `is_write`, a single `<Context: RequestContext>` generic, a `ctx:
+/// Context` second argument, and a `ProtocolResult<String>` return type.
+pub fn verify_read_or_write(item: TokenStream, is_write: bool) -> TokenStream {
+    let method = syn::parse_macro_input!(item as ImplItemMethod);
+    let name = attr_name(is_write);
+
+    if !matches!(method.vis, Visibility::Inherited) {
+        panic!("#[{}]: method must be private", name);
+    }
+
+    match method.sig.inputs.first() {
+        Some(FnArg::Receiver(receiver)) if receiver.mutability.is_some() == is_write => {}
+        _ => panic!(
+            "#[{}]: method must take `{}` as its receiver",
+            name,
+            if is_write { "&mut self" } else { "&self" },
+        ),
+    }
+
+    let Some(context_param) = request_context_param(&method) else {
+        panic!(
+            "#[{}]: method must declare a single `<T: RequestContext>` generic",
+            name
+        );
+    };
+
+    match method.sig.inputs.iter().nth(1) {
+        Some(FnArg::Typed(pat_type)) if type_path_ends_in(&pat_type.ty, &context_param.to_string()) => {}
+        _ => panic!(
+            "#[{}]: method must take its context generic as its second argument",
+            name
+        ),
+    }
+
+    if !returns_protocol_result_string(&method) {
+        panic!("#[{}]: method must return `ProtocolResult<String>`", name);
+    }
+
+    quote::quote!(#method).into()
+}
@@ -0,0 +1,378 @@
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::{format_ident, quote};
+use syn::{parse_macro_input, AttributeArgs, ImplItem, ItemImpl, Lit, Meta, NestedMeta, Type};
+
+use crate::common::{cycles_literal, has_attr, payload_type};
+
+/// The wire codec a `#[service]` dispatches payloads with.
+enum Codec {
+    Json,
+    Protobuf,
+}
+
+/// A single `#[read]`/`#[write]` method collected off the `impl` block.
+struct ServiceMethod {
+    ident: syn::Ident,
+    payload_ty: Option<Type>,
+    cycles: Option<u64>,
+}
+
+/// Parsed `#[service(..)]` attribute arguments.
+struct ServiceAttr {
+    codec: Codec,
+    /// `#[service(trace)]`: wrap every dispatch arm in a tracing span.
+    trace: bool,
+}
+
+/// Parses the `codec = "..."` and `trace` keys out of `#[service(..)]`'s
+/// attribute args. `codec` defaults to `Codec::Json`, `trace` defaults to
+/// `false` when absent.
+fn parse_service_attr(attr: &AttributeArgs) -> ServiceAttr {
+    let mut codec = Codec::Json;
+    let mut trace = false;
+
+    for meta in attr {
+        match meta {
+            NestedMeta::Meta(Meta::NameValue(nv)) if nv.path.is_ident("codec") => {
+                if let Lit::Str(s) = &nv.lit {
+                    codec = match s.value().as_str() {
+                        "protobuf" => Codec::Protobuf,
+                        "json" => Codec::Json,
+                        other => panic!("#[service]: unsupported codec `{}`, expected `json` or `protobuf`", other),
+                    };
+                }
+            }
+            NestedMeta::Meta(Meta::Path(path)) if path.is_ident("trace") => {
+                trace = true;
+            }
+            _ => {}
+        }
+    }
+
+    ServiceAttr { codec, trace }
+}
+
+/// JSON-RPC 2.0 reserved error codes the generated dispatch maps its own
+/// failure modes onto, per https://www.jsonrpc.org/specification#error_object.
+const JSON_RPC_PARSE_ERROR: i64 = -32700;
+const JSON_RPC_INVALID_PARAMS: i64 = -32602;
+const JSON_RPC_METHOD_NOT_FOUND: i64 = -32601;
+
+/// Generates the event logged for a dispatch failure, when `#[service(trace)]`
+/// is active. Shared between the decode step and the method call itself so
+/// neither failure mode goes unlogged.
+fn trace_error_event(service: &str, name: &str, writeable: bool) -> TokenStream2 {
+    quote! {
+        tracing::event!(tracing::Level::ERROR, service = #service, method = #name, writeable = #writeable, error = %e, "service call failed");
+    }
+}
+
+/// Generates the expression that decodes a dispatched method's payload off
+/// `ctx`, per the service's chosen codec. Decode failures are tagged with a
+/// JSON-RPC 2.0 code via `ServiceError::with_code` so RPC callers get a
+/// spec-compliant code instead of an opaque string, and, when tracing is
+/// active, logged the same way a failure from the method body itself is.
+fn decode_payload_expr(
+    codec: &Codec,
+    ty: &Type,
+    trace: bool,
+    service: &str,
+    name: &str,
+    writeable: bool,
+) -> TokenStream2 {
+    let log = trace.then(|| trace_error_event(service, name, writeable));
+
+    match codec {
+        Codec::Json => quote! {
+            let payload: #ty = serde_json::from_str(ctx.get_payload())
+                .map_err(|e| {
+                    let e = core_binding::ServiceError::JsonParse(e).with_code(#JSON_RPC_PARSE_ERROR);
+                    #log
+                    e
+                })?;
+        },
+        Codec::Protobuf => quote! {
+            let payload: #ty = <#ty as prost::Message>::decode(ctx.get_payload_bytes())
+                .map_err(|e| {
+                    let e = core_binding::ServiceError::Decode(e).with_code(#JSON_RPC_INVALID_PARAMS);
+                    #log
+                    e
+                })?;
+        },
+    }
+}
+
+fn collect_methods(item_impl: &ItemImpl, attr_name: &str) -> Vec<ServiceMethod> {
+    item_impl
+        .items
+        .iter()
+        .filter_map(|item| match item {
+            ImplItem::Method(method) if has_attr(method, attr_name) => Some(ServiceMethod {
+                ident: method.sig.ident.clone(),
+                payload_ty: payload_type(method),
+                cycles: cycles_literal(method),
+            }),
+            _ => None,
+        })
+        .collect()
+}
+
+fn gen_dispatch_arm(
+    codec: &Codec,
+    trace: bool,
+    service: &str,
+    writeable: bool,
+    method: &ServiceMethod,
+) -> TokenStream2 {
+    let ServiceMethod { ident, payload_ty, .. } = method;
+    let name = ident.to_string();
+
+    let call = match payload_ty {
+        Some(ty) => {
+            let decode = decode_payload_expr(codec, ty, trace, service, &name, writeable);
+            quote! {
+                #decode
+                self.#ident(ctx, payload).map_err(core_binding::ensure_rpc_code)
+            }
+        }
+        None => quote! { self.#ident(ctx).map_err(core_binding::ensure_rpc_code) },
+    };
+
+    let call = if trace {
+        let log = trace_error_event(service, &name, writeable);
+        quote! {
+            let _span = tracing::info_span!("service_call", service = #service, method = #name, writeable = #writeable).entered();
+            #call.map_err(|e| {
+                #log
+                e
+            })
+        }
+    } else {
+        call
+    };
+
+    quote! {
+        #name => {
+            #call
+        }
+    }
+}
+
+fn gen_dispatch_fn(
+    fn_name: syn::Ident,
+    receiver: TokenStream2,
+    codec: &Codec,
+    trace: bool,
+    service: &str,
+    writeable: bool,
+    methods: &[ServiceMethod],
+) -> TokenStream2 {
+    let arms = methods
+        .iter()
+        .map(|method| gen_dispatch_arm(codec, trace, service, writeable, method));
+
+    quote! {
+        fn #fn_name<Context: protocol::traits::RequestContext>(
+            #receiver,
+            ctx: Context,
+        ) -> protocol::ProtocolResult<String> {
+            let method = ctx.get_service_method();
+
+            match method {
+                #(#arms)*
+                _ => Err(core_binding::ServiceError::NotFoundMethod(method.to_owned())
+                    .with_code(#JSON_RPC_METHOD_NOT_FOUND)
+                    .into()),
+            }
+        }
+    }
+}
+
+/// Generates the expression that serializes a client call's payload (under
+/// the JSON codec, `()` when the method takes none; under the protobuf codec,
+/// an empty buffer), dispatches it to the service under `name` per the
+/// service's chosen codec, and returns the callee's raw `String` response
+/// verbatim -- dispatch never encodes its response, so the client must not
+/// try to decode one either.
+fn gen_client_call(codec: &Codec, service: &str, method: &ServiceMethod) -> TokenStream2 {
+    let name = method.ident.to_string();
+    let has_payload = method.payload_ty.is_some();
+
+    match (codec, has_payload) {
+        (Codec::Json, _) => {
+            let payload = if has_payload {
+                quote! { payload }
+            } else {
+                quote! { () }
+            };
+            quote! {
+                let payload = serde_json::to_string(&#payload)
+                    .map_err(core_binding::ServiceError::JsonParse)?;
+                ctx.dispatch_service(#service, #name, payload)
+            }
+        }
+        (Codec::Protobuf, true) => quote! {
+            let payload = prost::Message::encode_to_vec(&payload);
+            ctx.dispatch_service_bytes(#service, #name, payload)
+        },
+        (Codec::Protobuf, false) => quote! {
+            ctx.dispatch_service_bytes(#service, #name, Vec::new())
+        },
+    }
+}
+
+/// Generates a single `<Name>Client` method mirroring a `#[read]`/`#[write]`
+/// method, taking the same payload type and returning the callee's raw
+/// `String` response, matching the `ProtocolResult<String>` every
+/// `#[read]`/`#[write]` method returns.
+fn gen_client_method(codec: &Codec, service: &str, method: &ServiceMethod) -> TokenStream2 {
+    let ServiceMethod { ident, payload_ty, .. } = method;
+    let call = gen_client_call(codec, service, method);
+
+    match payload_ty {
+        Some(ty) => quote! {
+            pub fn #ident(
+                ctx: Context,
+                payload: #ty,
+            ) -> protocol::ProtocolResult<String> {
+                #call
+            }
+        },
+        None => quote! {
+            pub fn #ident(
+                ctx: Context,
+            ) -> protocol::ProtocolResult<String> {
+                #call
+            }
+        },
+    }
+}
+
+/// Generates a `<Name>Client` struct whose methods mirror every
+/// `#[read]`/`#[write]` method on the service, so callers get the same type
+/// checking as calling the service directly instead of hand-building a
+/// method name and payload.
+fn gen_client_code(
+    self_ty: &syn::Type,
+    codec: &Codec,
+    read_methods: &[ServiceMethod],
+    write_methods: &[ServiceMethod],
+) -> TokenStream2 {
+    let client_ident = format_ident!("{}Client", quote!(#self_ty).to_string());
+    let service = quote!(#self_ty).to_string();
+
+    let read_fns = read_methods
+        .iter()
+        .map(|method| gen_client_method(codec, &service, method));
+    let write_fns = write_methods
+        .iter()
+        .map(|method| gen_client_method(codec, &service, method));
+
+    quote! {
+        pub struct #client_ident<Context> {
+            _phantom: std::marker::PhantomData<Context>,
+        }
+
+        impl<Context: protocol::traits::RequestContext> #client_ident<Context> {
+            #(#read_fns)*
+            #(#write_fns)*
+        }
+    }
+}
+
+/// Describes a single `#[read]`/`#[write]` method in `__service_schema`'s
+/// output.
+#[derive(serde::Serialize)]
+struct MethodSchema {
+    name: String,
+    kind: &'static str,
+    payload_type: Option<String>,
+    cycles: Option<u64>,
+}
+
+fn method_schema(kind: &'static str, method: &ServiceMethod) -> MethodSchema {
+    MethodSchema {
+        name: method.ident.to_string(),
+        kind,
+        payload_type: method
+            .payload_ty
+            .as_ref()
+            .map(|ty| quote!(#ty).to_string()),
+        cycles: method.cycles,
+    }
+}
+
+/// Generates a `__service_schema` function returning a stable JSON
+/// description of the service's interface, so tooling and client generators
+/// can discover callable methods and their shapes without parsing source.
+fn gen_schema_code(
+    self_ty: &syn::Type,
+    read_methods: &[ServiceMethod],
+    write_methods: &[ServiceMethod],
+) -> TokenStream2 {
+    let schema: Vec<MethodSchema> = read_methods
+        .iter()
+        .map(|m| method_schema("read", m))
+        .chain(write_methods.iter().map(|m| method_schema("write", m)))
+        .collect();
+    let schema =
+        serde_json::to_string(&schema).expect("service method schema is always serializable");
+
+    quote! {
+        impl #self_ty {
+            pub fn __service_schema() -> &'static str {
+                #schema
+            }
+        }
+    }
+}
+
+/// Generates `impl protocol::traits::Service for <Name>` off an
+/// `impl <Name> { ... }` block annotated with `#[service]`.
+pub fn gen_service_code(attr: TokenStream, item: TokenStream) -> TokenStream {
+    let attr = parse_macro_input!(attr as AttributeArgs);
+    let item_impl = parse_macro_input!(item as ItemImpl);
+
+    let ServiceAttr { codec, trace } = parse_service_attr(&attr);
+    let self_ty = &item_impl.self_ty;
+    let service = quote!(#self_ty).to_string();
+
+    let read_methods = collect_methods(&item_impl, "read");
+    let write_methods = collect_methods(&item_impl, "write");
+
+    let read_fn = gen_dispatch_fn(
+        syn::Ident::new("read", proc_macro2::Span::call_site()),
+        quote! { &self },
+        &codec,
+        trace,
+        &service,
+        false,
+        &read_methods,
+    );
+    let write_fn = gen_dispatch_fn(
+        syn::Ident::new("write", proc_macro2::Span::call_site()),
+        quote! { &mut self },
+        &codec,
+        trace,
+        &service,
+        true,
+        &write_methods,
+    );
+    let client = gen_client_code(self_ty, &codec, &read_methods, &write_methods);
+    let schema = gen_schema_code(self_ty, &read_methods, &write_methods);
+
+    let expanded = quote! {
+        #item_impl
+
+        impl protocol::traits::Service for #self_ty {
+            #read_fn
+            #write_fn
+        }
+
+        #client
+        #schema
+    };
+
+    expanded.into()
+}
@@ -0,0 +1,42 @@
+use binding_macro::{read, service, write};
+use protocol::traits::RequestContext;
+use protocol::ProtocolResult;
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize)]
+struct GetKittyPayload {
+    id: String,
+}
+
+struct KittyService;
+
+#[service]
+impl KittyService {
+    #[read]
+    fn get_kitty<Context: RequestContext>(
+        &self,
+        _ctx: Context,
+        payload: GetKittyPayload,
+    ) -> ProtocolResult<String> {
+        Ok(payload.id)
+    }
+
+    #[write]
+    fn ping<Context: RequestContext>(&mut self, _ctx: Context) -> ProtocolResult<String> {
+        Ok("pong".to_owned())
+    }
+}
+
+fn use_client<Context: RequestContext>(ctx: Context) -> ProtocolResult<()> {
+    let id: String = KittyServiceClient::get_kitty(
+        ctx.clone(),
+        GetKittyPayload {
+            id: "1".to_owned(),
+        },
+    )?;
+    let pong: String = KittyServiceClient::ping(ctx)?;
+    let _ = (id, pong);
+    Ok(())
+}
+
+fn main() {}
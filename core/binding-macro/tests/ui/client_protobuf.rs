@@ -0,0 +1,44 @@
+use binding_macro::{read, service, write};
+use protocol::traits::RequestContext;
+use protocol::ProtocolResult;
+
+#[derive(Clone, PartialEq, prost::Message)]
+struct GetKittyPayload {
+    #[prost(string, tag = "1")]
+    id: String,
+}
+
+struct KittyService;
+
+#[service(codec = "protobuf")]
+impl KittyService {
+    #[read]
+    fn get_kitty<Context: RequestContext>(
+        &self,
+        _ctx: Context,
+        payload: GetKittyPayload,
+    ) -> ProtocolResult<String> {
+        Ok(payload.id)
+    }
+
+    #[write]
+    fn ping<Context: RequestContext>(&mut self, _ctx: Context) -> ProtocolResult<String> {
+        Ok(String::new())
+    }
+}
+
+fn use_client<Context: RequestContext>(ctx: Context) -> ProtocolResult<()> {
+    // Exercises the payload and no-payload client call paths under the
+    // protobuf codec: neither should try to encode `()` as a prost message.
+    let id: String = KittyServiceClient::get_kitty(
+        ctx.clone(),
+        GetKittyPayload {
+            id: "1".to_owned(),
+        },
+    )?;
+    let pong: String = KittyServiceClient::ping(ctx)?;
+    let _ = (id, pong);
+    Ok(())
+}
+
+fn main() {}
@@ -0,0 +1,25 @@
+use binding_macro::{read, service};
+use protocol::traits::RequestContext;
+use protocol::ProtocolResult;
+
+#[derive(Clone, PartialEq, prost::Message)]
+struct GetKittyPayload {
+    #[prost(string, tag = "1")]
+    id: String,
+}
+
+struct KittyService;
+
+#[service(codec = "protobuf")]
+impl KittyService {
+    #[read]
+    fn get_kitty<Context: RequestContext>(
+        &self,
+        _ctx: Context,
+        payload: GetKittyPayload,
+    ) -> ProtocolResult<String> {
+        Ok(payload.id)
+    }
+}
+
+fn main() {}
@@ -0,0 +1,26 @@
+use binding_macro::{cycles, read, service};
+use protocol::traits::RequestContext;
+use protocol::ProtocolResult;
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize)]
+struct GetKittyPayload {
+    data: Vec<u8>,
+}
+
+struct KittyService;
+
+#[service]
+impl KittyService {
+    #[read]
+    #[cycles(expr = 21_000 + payload.data.len() as u64 * 68)]
+    fn get_kitty<Context: RequestContext>(
+        &self,
+        ctx: Context,
+        payload: GetKittyPayload,
+    ) -> ProtocolResult<String> {
+        Ok(payload.data.len().to_string())
+    }
+}
+
+fn main() {}
@@ -0,0 +1,32 @@
+use binding_macro::{read, service};
+use protocol::traits::RequestContext;
+use protocol::ProtocolResult;
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize)]
+struct GetKittyPayload {
+    id: String,
+}
+
+struct KittyService;
+
+#[service]
+impl KittyService {
+    #[read]
+    fn get_kitty<Context: RequestContext>(
+        &self,
+        _ctx: Context,
+        payload: GetKittyPayload,
+    ) -> ProtocolResult<String> {
+        if payload.id.is_empty() {
+            // An author-supplied code should survive dispatch untouched.
+            return Err(core_binding::ServiceError::NotFoundMethod("no such kitty".to_owned())
+                .with_code(-32004)
+                .into());
+        }
+
+        Ok(payload.id)
+    }
+}
+
+fn main() {}
@@ -0,0 +1,35 @@
+use binding_macro::{cycles, read, service, write};
+use protocol::traits::RequestContext;
+use protocol::ProtocolResult;
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize)]
+struct GetKittyPayload {
+    id: String,
+}
+
+struct KittyService;
+
+#[service]
+impl KittyService {
+    #[read]
+    #[cycles(100)]
+    fn get_kitty<Context: RequestContext>(
+        &self,
+        ctx: Context,
+        payload: GetKittyPayload,
+    ) -> ProtocolResult<String> {
+        Ok(payload.id)
+    }
+
+    #[write]
+    fn ping<Context: RequestContext>(&mut self, _ctx: Context) -> ProtocolResult<String> {
+        Ok("pong".to_owned())
+    }
+}
+
+fn main() {
+    let schema: serde_json::Value = serde_json::from_str(KittyService::__service_schema())
+        .expect("__service_schema must emit valid JSON");
+    assert!(schema.is_array());
+}
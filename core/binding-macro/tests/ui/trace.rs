@@ -0,0 +1,29 @@
+use binding_macro::{read, service};
+use protocol::traits::RequestContext;
+use protocol::ProtocolResult;
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize)]
+struct GetKittyPayload {
+    id: String,
+}
+
+struct KittyService;
+
+// Checks that the code `#[service(trace)]` generates compiles, including the
+// logging on both failure paths: a payload decode failure (short-circuits
+// via `?` before the method body runs) and an error returned by the method
+// body itself. Nothing here is run.
+#[service(trace)]
+impl KittyService {
+    #[read]
+    fn get_kitty<Context: RequestContext>(
+        &self,
+        _ctx: Context,
+        payload: GetKittyPayload,
+    ) -> ProtocolResult<String> {
+        Ok(payload.id)
+    }
+}
+
+fn main() {}
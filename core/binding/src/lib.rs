@@ -0,0 +1,115 @@
+//! Runtime support consumed by the code `#[service]`/`#[read]`/`#[write]`/
+//! `#[cycles]` generate in `binding-macro`. Contract authors do not call
+//! into this crate directly; the macros reference it by name
+//! (`core_binding::...`) in their generated output.
+
+use thiserror::Error;
+
+/// Errors produced by service dispatch generated via `#[service]`.
+#[derive(Debug, Error)]
+pub enum ServiceError {
+    #[error("can not found method:{0}")]
+    NotFoundMethod(String),
+
+    #[error("parse payload to json failed: {0}")]
+    JsonParse(#[source] serde_json::Error),
+
+    #[error("decode protobuf payload failed: {0}")]
+    Decode(#[source] prost::DecodeError),
+}
+
+impl ServiceError {
+    /// Tags this error with a JSON-RPC 2.0 error code (e.g. `-32601` for
+    /// "method not found"), so it survives end to end and the RPC layer can
+    /// emit a spec-compliant `{code, message, data}` object.
+    pub fn with_code(self, code: i64) -> CodedServiceError {
+        CodedServiceError {
+            code,
+            message: self.to_string(),
+            source: Box::new(self),
+        }
+    }
+}
+
+/// Any error, carrying a JSON-RPC 2.0 error code, produced while dispatching
+/// a service method. Built either by the generated dispatch itself (via
+/// `ServiceError::with_code`, or via [`ensure_rpc_code`] for an error that
+/// carries no code) or by a service author tagging their own `ProtocolResult`
+/// error (via `ServiceError::with_code`, or by building a `CodedServiceError`
+/// around their own error type).
+#[derive(Debug, Error)]
+#[error("{message}")]
+pub struct CodedServiceError {
+    pub code: i64,
+    pub message: String,
+    #[source]
+    pub source: Box<dyn std::error::Error + Send + Sync + 'static>,
+}
+
+/// JSON-RPC 2.0 reserves `-32000..-32099` for implementation-defined server
+/// errors. A service author's own error lands here unless they attach a
+/// more specific code themselves (e.g. via `ServiceError::with_code`, or by
+/// building a `CodedServiceError` around their own error type).
+pub const JSON_RPC_SERVER_ERROR: i64 = -32000;
+
+/// Ensures a dispatched method's result carries a JSON-RPC 2.0 error code:
+/// preserves one a service author already attached, otherwise tags it with
+/// [`JSON_RPC_SERVER_ERROR`] so every error family surfaces a code.
+pub fn ensure_rpc_code(err: protocol::ProtocolError) -> protocol::ProtocolError {
+    if err.downcast_ref::<CodedServiceError>().is_some() {
+        return err;
+    }
+
+    CodedServiceError {
+        code: JSON_RPC_SERVER_ERROR,
+        message: err.to_string(),
+        source: Box::new(err),
+    }
+    .into()
+}
+
+impl From<ServiceError> for protocol::ProtocolError {
+    fn from(err: ServiceError) -> Self {
+        protocol::ProtocolError::new(protocol::ProtocolErrorKind::Service, Box::new(err))
+    }
+}
+
+impl From<CodedServiceError> for protocol::ProtocolError {
+    fn from(err: CodedServiceError) -> Self {
+        protocol::ProtocolError::new(protocol::ProtocolErrorKind::Service, Box::new(err))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ensure_rpc_code_preserves_an_authors_own_code() {
+        let err: protocol::ProtocolError = ServiceError::NotFoundMethod("no such kitty".to_owned())
+            .with_code(-32004)
+            .into();
+
+        let err = ensure_rpc_code(err);
+
+        let coded = err
+            .downcast_ref::<CodedServiceError>()
+            .expect("author's CodedServiceError must survive ensure_rpc_code");
+        assert_eq!(coded.code, -32004);
+    }
+
+    #[test]
+    fn ensure_rpc_code_defaults_an_uncoded_error() {
+        let err: protocol::ProtocolError = ServiceError::JsonParse(
+            serde_json::from_str::<()>("not json").unwrap_err(),
+        )
+        .into();
+
+        let err = ensure_rpc_code(err);
+
+        let coded = err
+            .downcast_ref::<CodedServiceError>()
+            .expect("ensure_rpc_code must attach a code when the author didn't");
+        assert_eq!(coded.code, JSON_RPC_SERVER_ERROR);
+    }
+}
@@ -0,0 +1,65 @@
+//! Shared protocol types consumed across the framework: the `ProtocolError`/
+//! `ProtocolResult` error plumbing and the `traits` contracts that
+//! `binding-macro`'s generated code targets.
+
+pub mod traits;
+
+use std::fmt;
+
+pub type ProtocolResult<T> = Result<T, ProtocolError>;
+
+/// Which subsystem a [`ProtocolError`] originated from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProtocolErrorKind {
+    Service,
+}
+
+impl fmt::Display for ProtocolErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ProtocolErrorKind::Service => write!(f, "Service"),
+        }
+    }
+}
+
+/// The error type threaded through every `ProtocolResult`. Wraps the
+/// underlying error as a boxed `dyn Error` so callers can `downcast_ref`
+/// back to the concrete type they (or a macro acting on their behalf) threw,
+/// e.g. to recover a `core_binding::CodedServiceError`'s JSON-RPC code.
+#[derive(Debug)]
+pub struct ProtocolError {
+    kind: ProtocolErrorKind,
+    source: Box<dyn std::error::Error + Send + Sync + 'static>,
+}
+
+impl ProtocolError {
+    pub fn new(
+        kind: ProtocolErrorKind,
+        source: Box<dyn std::error::Error + Send + Sync + 'static>,
+    ) -> Self {
+        ProtocolError { kind, source }
+    }
+
+    pub fn kind(&self) -> ProtocolErrorKind {
+        self.kind
+    }
+
+    /// Downcasts the boxed source error back to a concrete type, e.g. to
+    /// recover a JSON-RPC code a service author attached via
+    /// `core_binding::ServiceError::with_code`.
+    pub fn downcast_ref<E: std::error::Error + 'static>(&self) -> Option<&E> {
+        self.source.downcast_ref::<E>()
+    }
+}
+
+impl fmt::Display for ProtocolError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "[{}] {}", self.kind, self.source)
+    }
+}
+
+impl std::error::Error for ProtocolError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        Some(self.source.as_ref())
+    }
+}
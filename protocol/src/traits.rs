@@ -0,0 +1,40 @@
+use crate::ProtocolResult;
+
+/// The per-call context `#[service]`'s generated dispatch hands to every
+/// `#[read]`/`#[write]` method.
+pub trait RequestContext: Clone {
+    /// The service method being dispatched, e.g. `"get_kitty"`.
+    fn get_service_method(&self) -> &str;
+
+    /// The call's payload, for services using the `"json"` codec.
+    fn get_payload(&self) -> &str;
+
+    /// The call's payload, for services using the `"protobuf"` codec.
+    fn get_payload_bytes(&self) -> &[u8];
+
+    /// Deducts `amount` cycles from the call's budget, short-circuiting the
+    /// caller via `?` when the budget is exhausted.
+    fn sub_cycles(&self, amount: u64) -> ProtocolResult<()>;
+
+    /// Calls `method` on `service` with a string-encoded payload (for a
+    /// `"json"`-codec callee), returning its raw `String` response verbatim
+    /// -- `#[service]`-generated `<Name>Client`s call this for cross-service
+    /// calls.
+    fn dispatch_service(&self, service: &str, method: &str, payload: String) -> ProtocolResult<String>;
+
+    /// As [`Self::dispatch_service`], for a `"protobuf"`-codec callee.
+    fn dispatch_service_bytes(
+        &self,
+        service: &str,
+        method: &str,
+        payload: Vec<u8>,
+    ) -> ProtocolResult<String>;
+}
+
+/// The trait `#[service]` generates an implementation of off an annotated
+/// `impl <Name> { .. }` block.
+pub trait Service {
+    fn read<Context: RequestContext>(&self, ctx: Context) -> ProtocolResult<String>;
+
+    fn write<Context: RequestContext>(&mut self, ctx: Context) -> ProtocolResult<String>;
+}